@@ -15,8 +15,9 @@ use crate::utils::set_panic_hook;
 
 use crate::{BbsVerifyResponse, PoKOfSignatureProofMultiWrapper, PoKOfSignatureProofWrapper};
 use bbs::prelude::*;
+use ff_zeroize::{Field, PrimeField};
 use pairing_plus::{
-    bls12_381::{Bls12, Fr, G1, G2},
+    bls12_381::{Bls12, Fr, FrRepr, G1, G2},
     hash_to_field::BaseFromRO,
     serdes::SerDes,
     CurveProjective,
@@ -30,6 +31,7 @@ use std::{
     iter::FromIterator,
 };
 use wasm_bindgen::prelude::*;
+use zeroize::Zeroize;
 
 use itertools::multizip;
 
@@ -76,7 +78,8 @@ wasm_impl!(
     publicKey: DeterministicPublicKey,
     messages: Vec<Vec<u8>>,
     revealed: Vec<usize>,
-    nonce: Vec<u8>
+    nonce: Vec<u8>,
+    presentationHeader: Vec<u8>
 );
 
 wasm_impl!(
@@ -84,7 +87,8 @@ wasm_impl!(
     proof: PoKOfSignatureProofWrapper,
     publicKey: DeterministicPublicKey,
     messages: Vec<Vec<u8>>,
-    nonce: Vec<u8>
+    nonce: Vec<u8>,
+    presentationHeader: Vec<u8>
 );
 
 wasm_impl!(
@@ -94,7 +98,8 @@ wasm_impl!(
     messages: Vec<Vec<Vec<u8>>>,
     revealed: Vec<Vec<usize>>,
     nonce: Vec<u8>,
-    equivs: Vec<Vec<(usize, usize)>>
+    equivs: Vec<Vec<(usize, usize)>>,
+    presentationHeader: Vec<u8>
 );
 
 wasm_impl!(
@@ -102,9 +107,96 @@ wasm_impl!(
     proof: Vec<PoKOfSignatureProofWrapper>,
     publicKey: Vec<DeterministicPublicKey>,
     messages: Vec<Vec<Vec<u8>>>,
+    nonce: Vec<u8>,
+    equivs: Vec<Vec<(usize, usize)>>,
+    presentationHeader: Vec<u8>
+);
+
+wasm_impl!(
+    BlsSplitKeyRequest,
+    secretKey: Option<SecretKey>,
+    seed: Option<Vec<u8>>,
+    threshold: usize,
+    totalShares: usize
+);
+
+wasm_impl!(
+    #[allow(non_snake_case)]
+    #[derive(Debug, Clone, Deserialize, Serialize)]
+    BlsKeyShare,
+    index: usize,
+    share: SecretKey
+);
+
+wasm_impl!(
+    BlsSplitKeyResponse,
+    shares: Vec<BlsKeyShare>,
+    commitments: Vec<Vec<u8>>
+);
+
+wasm_impl!(
+    BlsReconstructKeyRequest,
+    shares: Vec<BlsKeyShare>,
+    commitments: Vec<Vec<u8>>
+);
+
+wasm_impl!(
+    BlsBlindCommitmentRequest,
+    publicKey: DeterministicPublicKey,
+    messageCount: usize,
+    hidden: Vec<usize>,
+    messages: Vec<Vec<u8>>,
+    nonce: Vec<u8>
+);
+
+wasm_impl!(
+    BlsBlindCommitmentContext,
+    commitment: BlindSignatureContext,
+    blindingFactor: SignatureBlinding
+);
+
+wasm_impl!(
+    BlsBlindSignRequest,
+    commitment: BlindSignatureContext,
+    keyPair: BlsKeyPair,
+    messageCount: usize,
+    known: Vec<usize>,
+    hidden: Vec<usize>,
+    messages: Vec<Vec<u8>>,
     nonce: Vec<u8>
 );
 
+wasm_impl!(
+    BlsUnblindSignatureRequest,
+    signature: BlindSignature,
+    blindingFactor: SignatureBlinding
+);
+
+wasm_impl!(
+    #[allow(non_snake_case)]
+    #[derive(Debug, Clone, Deserialize, Serialize)]
+    Jwk,
+    kty: String,
+    crv: String,
+    alg: String,
+    x: String,
+    d: Option<String>,
+    messageCount: Option<usize>
+);
+
+wasm_impl!(
+    BlsKeyPairToJwkRequest,
+    keyPair: BlsKeyPair,
+    curve: String,
+    messageCount: Option<usize>
+);
+
+wasm_impl!(
+    BlsKeyPairFromJwkResponse,
+    keyPair: BlsKeyPair,
+    messageCount: Option<usize>
+);
+
 /// Generate a BLS 12-381 key pair.
 ///
 /// * seed: UIntArray with 32 element
@@ -234,6 +326,315 @@ pub async fn bls_verify(request: JsValue) -> Result<JsValue, JsValue> {
     }
 }
 
+const BASE64URL_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+
+/// Encodes `bytes` as unpadded base64url (RFC 4648 section 5), the encoding
+/// JWK uses for its `x`/`d` coordinate members.
+fn base64url_encode(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0] as u32;
+        let b1 = *chunk.get(1).unwrap_or(&0) as u32;
+        let b2 = *chunk.get(2).unwrap_or(&0) as u32;
+        let n = (b0 << 16) | (b1 << 8) | b2;
+        out.push(BASE64URL_ALPHABET[((n >> 18) & 0x3f) as usize] as char);
+        out.push(BASE64URL_ALPHABET[((n >> 12) & 0x3f) as usize] as char);
+        if chunk.len() > 1 {
+            out.push(BASE64URL_ALPHABET[((n >> 6) & 0x3f) as usize] as char);
+        }
+        if chunk.len() > 2 {
+            out.push(BASE64URL_ALPHABET[(n & 0x3f) as usize] as char);
+        }
+    }
+    out
+}
+
+/// Decodes unpadded base64url as produced by [`base64url_encode`].
+fn base64url_decode(s: &str) -> Result<Vec<u8>, String> {
+    fn value(c: u8) -> Result<u32, String> {
+        match c {
+            b'A'..=b'Z' => Ok((c - b'A') as u32),
+            b'a'..=b'z' => Ok((c - b'a' + 26) as u32),
+            b'0'..=b'9' => Ok((c - b'0' + 52) as u32),
+            b'-' => Ok(62),
+            b'_' => Ok(63),
+            _ => Err("invalid base64url character".to_string()),
+        }
+    }
+    let chars: Vec<u8> = s.bytes().collect();
+    let mut out = Vec::with_capacity(chars.len() * 3 / 4);
+    for chunk in chars.chunks(4) {
+        let mut n: u32 = 0;
+        for (i, &c) in chunk.iter().enumerate() {
+            n |= value(c)? << (18 - i * 6);
+        }
+        out.push((n >> 16) as u8);
+        if chunk.len() > 2 {
+            out.push((n >> 8) as u8);
+        }
+        if chunk.len() > 3 {
+            out.push(n as u8);
+        }
+    }
+    Ok(out)
+}
+
+/// Exports a `BlsKeyPair` (as produced by `generateBls12381G1KeyPair` /
+/// `generateBls12381G2KeyPair`, or the compact `DeterministicPublicKey`
+/// a `BbsKeyPair` is expanded from) to a JWK. The curve member distinguishes the G1
+/// and G2 variants, and `messageCount` is round-tripped through a JWK
+/// extension member so the key can be re-expanded with `bls12381toBbs`.
+#[wasm_bindgen(js_name = blsKeyPairToJwk)]
+pub async fn bls_key_pair_to_jwk(request: JsValue) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let request: BlsKeyPairToJwkRequest = request.try_into()?;
+    let (crv, expected_len) = match request.curve.as_str() {
+        "BLS12381_G1" => ("BLS12381G1", G1_COMPRESSED_SIZE),
+        "BLS12381_G2" => ("BLS12381G2", G2_COMPRESSED_SIZE),
+        _ => return Err(JsValue::from_str("curve must be BLS12381_G1 or BLS12381_G2")),
+    };
+
+    let pk_bytes = request
+        .keyPair
+        .publicKey
+        .ok_or_else(|| JsValue::from_str("publicKey is required"))?;
+    if pk_bytes.len() != expected_len {
+        return Err(JsValue::from_str("publicKey length does not match curve"));
+    }
+
+    let jwk = Jwk {
+        kty: "OKP".to_string(),
+        crv: crv.to_string(),
+        alg: "BBS+".to_string(),
+        x: base64url_encode(&pk_bytes),
+        d: request
+            .keyPair
+            .secretKey
+            .map(|sk| base64url_encode(&sk.to_bytes_compressed_form())),
+        messageCount: request.messageCount,
+    };
+    Ok(serde_wasm_bindgen::to_value(&jwk).unwrap())
+}
+
+/// Imports a JWK produced by `blsKeyPairToJwk` back into a `BlsKeyPair`,
+/// rejecting anything whose curve or coordinate lengths don't match the
+/// expected `G1_COMPRESSED_SIZE`/`G2_COMPRESSED_SIZE`.
+#[wasm_bindgen(js_name = blsKeyPairFromJwk)]
+pub async fn bls_key_pair_from_jwk(request: JsValue) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let jwk: Jwk = request.try_into()?;
+    if jwk.kty != "OKP" || jwk.alg != "BBS+" {
+        return Err(JsValue::from_str("unsupported kty/alg"));
+    }
+    let expected_len = match jwk.crv.as_str() {
+        "BLS12381G1" => G1_COMPRESSED_SIZE,
+        "BLS12381G2" => G2_COMPRESSED_SIZE,
+        _ => return Err(JsValue::from_str("unsupported crv")),
+    };
+
+    let pk_bytes = base64url_decode(&jwk.x).map_err(|e| JsValue::from_str(&e))?;
+    if pk_bytes.len() != expected_len {
+        return Err(JsValue::from_str("x length does not match crv"));
+    }
+
+    let sk = match jwk.d {
+        Some(d) => {
+            let bytes = base64url_decode(&d).map_err(|e| JsValue::from_str(&e))?;
+            if bytes.len() != FR_COMPRESSED_SIZE {
+                return Err(JsValue::from_str("d length does not match crv"));
+            }
+            Some(SecretKey::from(array_ref![bytes, 0, FR_COMPRESSED_SIZE]))
+        }
+        None => None,
+    };
+
+    Ok(serde_wasm_bindgen::to_value(&BlsKeyPairFromJwkResponse {
+        keyPair: BlsKeyPair {
+            publicKey: Some(pk_bytes),
+            secretKey: sk,
+        },
+        messageCount: jwk.messageCount,
+    })
+    .unwrap())
+}
+
+/// Holder side of blind BBS+ issuance. Commits to the messages the holder
+/// wants to keep hidden from the issuer and attaches a Fiat-Shamir proof of
+/// knowledge of the blinding factor and the committed messages, so the
+/// issuer can check the commitment is well-formed without learning the
+/// hidden values.
+#[wasm_bindgen(js_name = blsBlindCommitment)]
+pub async fn bls_blind_commitment(request: JsValue) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let request: BlsBlindCommitmentRequest = request.try_into()?;
+    if request.hidden.len() != request.messages.len() {
+        return Err(JsValue::from_str(
+            "hidden indices and messages must have the same length",
+        ));
+    }
+    if request.hidden.iter().any(|i| *i >= request.messageCount) {
+        return Err(JsValue::from_str("hidden index is out of bounds"));
+    }
+
+    let pk = request.publicKey.to_public_key(request.messageCount)?;
+    let nonce = if request.nonce.is_empty() {
+        ProofNonce::default()
+    } else {
+        ProofNonce::hash(&request.nonce)
+    };
+
+    let mut hidden_messages = BTreeMap::new();
+    for (i, m) in request.hidden.iter().zip(request.messages.iter()) {
+        hidden_messages.insert(*i, SignatureMessage::hash(m));
+    }
+
+    match Prover::new_blind_signature_context(&pk, &hidden_messages, &nonce) {
+        Ok((commitment, blinding_factor)) => Ok(serde_wasm_bindgen::to_value(
+            &BlsBlindCommitmentContext {
+                commitment,
+                blindingFactor: blinding_factor,
+            },
+        )
+        .unwrap()),
+        Err(e) => Err(JsValue::from(&format!("{:?}", e))),
+    }
+}
+
+/// Issuer side of blind BBS+ issuance. Verifies the holder's commitment
+/// proof against the declared blinded indices and, only if it verifies,
+/// signs over the commitment plus the issuer-known revealed messages. The
+/// issuer never learns the hidden attribute values.
+#[wasm_bindgen(js_name = blsBlindSign)]
+pub async fn bls_blind_sign(request: JsValue) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let request: BlsBlindSignRequest = request.try_into()?;
+    let dpk_bytes = request
+        .keyPair
+        .publicKey
+        .ok_or_else(|| JsValue::from_str("Failed to convert key"))?;
+    let dpk = DeterministicPublicKey::from(array_ref![dpk_bytes, 0, G2_COMPRESSED_SIZE]);
+    let pk = dpk.to_public_key(request.messageCount)?;
+    let sk = request
+        .keyPair
+        .secretKey
+        .ok_or_else(|| JsValue::from_str("Failed to sign"))?;
+
+    if request.known.len() != request.messages.len() {
+        return Err(JsValue::from_str(
+            "known indices and messages must have the same length",
+        ));
+    }
+    let known_set: BTreeSet<usize> = request.known.iter().cloned().collect();
+    let hidden_set: BTreeSet<usize> = request.hidden.iter().cloned().collect();
+    let all_slots: BTreeSet<usize> = (0..request.messageCount).collect();
+    if known_set.len() != request.known.len()
+        || hidden_set.len() != request.hidden.len()
+        || !known_set.is_disjoint(&hidden_set)
+        || known_set.union(&hidden_set).cloned().collect::<BTreeSet<usize>>() != all_slots
+    {
+        return Err(JsValue::from_str(
+            "known and hidden index sets must be disjoint and cover every message slot",
+        ));
+    }
+
+    let nonce = if request.nonce.is_empty() {
+        ProofNonce::default()
+    } else {
+        ProofNonce::hash(&request.nonce)
+    };
+
+    match request.commitment.verify(&hidden_set, &pk, &nonce) {
+        Ok(true) => {}
+        Ok(false) => return Err(JsValue::from_str("Commitment proof did not verify")),
+        Err(e) => return Err(JsValue::from(&format!("{:?}", e))),
+    }
+
+    let mut known_messages = BTreeMap::new();
+    for (i, m) in request.known.iter().zip(request.messages.iter()) {
+        known_messages.insert(*i, SignatureMessage::hash(m));
+    }
+
+    match Issuer::blind_sign(&request.commitment, &known_messages, &sk, &pk, &nonce) {
+        Ok(sig) => Ok(serde_wasm_bindgen::to_value(&sig).unwrap()),
+        Err(e) => Err(JsValue::from(&format!("{:?}", e))),
+    }
+}
+
+/// Holder side of blind BBS+ issuance. Removes the holder's blinding factor
+/// from a blind signature to recover a normal `Signature` that verifies
+/// under `blsVerify` like any other BBS+ signature.
+#[wasm_bindgen(js_name = blsUnblindSignature)]
+pub async fn bls_unblind_signature(request: JsValue) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let request: BlsUnblindSignatureRequest = request.try_into()?;
+    let signature = request.signature.to_unblinded(&request.blindingFactor);
+    Ok(serde_wasm_bindgen::to_value(&signature).unwrap())
+}
+
+/// Protocol label for the single-credential proof transcript. Mixing this
+/// in first means a challenge derived here can never collide with a
+/// challenge derived by the multi-credential transcript or by an unrelated
+/// protocol that happens to absorb the same bytes.
+const PROOF_TRANSCRIPT_LABEL: &[u8] = b"bbs-signatures/proof-of-knowledge/v1";
+
+/// Protocol label for the termwise multi-credential proof transcript.
+const PROOF_TRANSCRIPT_LABEL_MULTI: &[u8] = b"bbs-signatures/proof-of-knowledge-multi/v1";
+
+/// Append-only Fiat-Shamir transcript used to derive proof challenges.
+/// Every variable-length item is length-prefixed (integers are fixed-width
+/// and index sets are count-prefixed) so the byte stream cannot be
+/// reinterpreted as a different sequence of shorter or longer items, and
+/// every transcript starts from a fixed protocol label so this crate's
+/// challenges never collide with an unrelated protocol's.
+struct Transcript {
+    bytes: Vec<u8>,
+}
+
+impl Transcript {
+    fn new(label: &'static [u8]) -> Self {
+        let mut transcript = Transcript { bytes: Vec::new() };
+        transcript.append_message(label);
+        transcript
+    }
+
+    fn append_message(&mut self, message: &[u8]) {
+        self.bytes
+            .extend_from_slice(&(message.len() as u64).to_be_bytes());
+        self.bytes.extend_from_slice(message);
+    }
+
+    fn append_u64(&mut self, value: u64) {
+        self.bytes.extend_from_slice(&value.to_be_bytes());
+    }
+
+    fn append_indices(&mut self, indices: &BTreeSet<usize>) {
+        self.append_u64(indices.len() as u64);
+        for i in indices {
+            self.append_u64(*i as u64);
+        }
+    }
+
+    fn challenge(self) -> ProofChallenge {
+        ProofChallenge::hash(&self.bytes)
+    }
+}
+
+/// Encodes the verifier nonce for the transcript: `FR_COMPRESSED_SIZE` zero
+/// bytes when no nonce is supplied, otherwise the uncompressed form of the
+/// hashed nonce. The presentation header is absorbed as a separate item even
+/// when empty, so the challenge is never a plain hash of the PoK and nonce.
+fn nonce_transcript_bytes(nonce_input: &[u8]) -> Vec<u8> {
+    if nonce_input.is_empty() {
+        vec![0u8; FR_COMPRESSED_SIZE]
+    } else {
+        ProofNonce::hash(nonce_input)
+            .to_bytes_uncompressed_form()
+            .as_ref()
+            .to_vec()
+    }
+}
+
 /// Creates a BBS+ PoK
 #[wasm_bindgen(js_name = blsCreateProof)]
 pub async fn bls_create_proof(request: JsValue) -> Result<JsValue, JsValue> {
@@ -259,14 +660,14 @@ pub async fn bls_create_proof(request: JsValue) -> Result<JsValue, JsValue> {
     match PoKOfSignature::init(&request.signature, &pk, messages.as_slice()) {
         Err(e) => return Err(JsValue::from(&format!("{:?}", e))),
         Ok(pok) => {
-            let mut challenge_bytes = pok.to_bytes();
-            if request.nonce.is_empty() {
-                challenge_bytes.extend_from_slice(&[0u8; FR_COMPRESSED_SIZE]);
-            } else {
-                let nonce = ProofNonce::hash(&request.nonce);
-                challenge_bytes.extend_from_slice(nonce.to_bytes_uncompressed_form().as_ref());
-            }
-            let challenge_hash = ProofChallenge::hash(&challenge_bytes);
+            let mut transcript = Transcript::new(PROOF_TRANSCRIPT_LABEL);
+            transcript.append_message(&pk.to_bytes_compressed_form());
+            transcript.append_u64(request.messages.len() as u64);
+            transcript.append_indices(&revealed);
+            transcript.append_message(&pok.to_bytes());
+            transcript.append_message(&nonce_transcript_bytes(&request.nonce));
+            transcript.append_message(&request.presentationHeader);
+            let challenge_hash = transcript.challenge();
             match pok.gen_proof(&challenge_hash) {
                 Ok(proof) => {
                     let out =
@@ -296,24 +697,12 @@ pub async fn bls_verify_proof(request: JsValue) -> Result<JsValue, JsValue> {
         }
     };
 
-    let nonce = if request.nonce.is_empty() {
-        ProofNonce::default()
-    } else {
-        ProofNonce::hash(&request.nonce)
-    };
     let message_count = u16::from_be_bytes(*array_ref![request.proof.bit_vector, 0, 2]) as usize;
     let pk = request.publicKey.to_public_key(message_count)?;
     let messages = request.messages.clone();
     let (revealed, proof) = request.proof.unwrap();
-    let proof_request = ProofRequest {
-        revealed_messages: revealed,
-        verification_key: pk,
-    };
 
-    let revealed_vec = proof_request
-        .revealed_messages
-        .iter()
-        .collect::<Vec<&usize>>();
+    let revealed_vec = revealed.iter().collect::<Vec<&usize>>();
     let mut revealed_messages = BTreeMap::new();
     for i in 0..revealed_vec.len() {
         revealed_messages.insert(
@@ -322,13 +711,21 @@ pub async fn bls_verify_proof(request: JsValue) -> Result<JsValue, JsValue> {
         );
     }
 
-    let signature_proof = SignatureProof {
-        revealed_messages,
-        proof,
-    };
+    let mut transcript = Transcript::new(PROOF_TRANSCRIPT_LABEL);
+    transcript.append_message(&pk.to_bytes_compressed_form());
+    transcript.append_u64(message_count as u64);
+    transcript.append_indices(&revealed);
+    transcript.append_message(&proof.get_bytes_for_challenge(revealed.clone(), &pk));
+    transcript.append_message(&nonce_transcript_bytes(&request.nonce));
+    transcript.append_message(&request.presentationHeader);
+    let challenge_hash = transcript.challenge();
+
+    let verified = proof
+        .verify(&pk, &revealed_messages, &challenge_hash)
+        .unwrap_or(false);
 
     Ok(serde_wasm_bindgen::to_value(&BbsVerifyResponse {
-        verified: Verifier::verify_signature_pok(&proof_request, &signature_proof, &nonce).is_ok(),
+        verified,
         error: None,
     })
     .unwrap())
@@ -376,6 +773,196 @@ fn gen_sk(msg: &[u8]) -> Fr {
     Fr::from_okm(&result)
 }
 
+fn random_fr(rng: &mut impl RngCore) -> Fr {
+    let mut seed = vec![0u8; 32];
+    rng.fill_bytes(seed.as_mut_slice());
+    gen_sk(seed.as_slice())
+}
+
+fn fr_from_index(index: usize) -> Fr {
+    Fr::from_repr(FrRepr::from(index as u64)).unwrap()
+}
+
+/// Evaluates `f(x) = coefficients[0] + coefficients[1]*x + ...` at `x`.
+fn eval_polynomial(coefficients: &[Fr], x: Fr) -> Fr {
+    let mut result = Fr::zero();
+    let mut x_pow = Fr::one();
+    for c in coefficients {
+        let mut term = *c;
+        term.mul_assign(&x_pow);
+        result.add_assign(&term);
+        x_pow.mul_assign(&x);
+    }
+    result
+}
+
+/// Checks a Feldman-VSS share against the published polynomial commitments:
+/// `g2^{f(i)} == prod_k C_k^{i^k}`.
+fn verify_share(index: usize, share: &Fr, commitments: &[G2]) -> bool {
+    let mut lhs = G2::one();
+    lhs.mul_assign(*share);
+
+    let x = fr_from_index(index);
+    let mut rhs = G2::zero();
+    let mut x_pow = Fr::one();
+    for c in commitments {
+        let mut term = *c;
+        term.mul_assign(x_pow);
+        rhs.add_assign(&term);
+        x_pow.mul_assign(&x);
+    }
+    lhs == rhs
+}
+
+/// Splits a BBS issuer secret key into a `threshold`-of-`totalShares` Shamir
+/// sharing so that the key never needs to exist whole outside of signing
+/// time. Each share is accompanied by Feldman commitments to the sharing
+/// polynomial so a custodian can verify its own share came from a
+/// consistent dealer without a trusted third party.
+#[wasm_bindgen(js_name = blsSplitKey)]
+pub async fn bls_split_key(request: JsValue) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let request: BlsSplitKeyRequest = request.try_into()?;
+    if request.threshold == 0 || request.totalShares == 0 || request.threshold > request.totalShares
+    {
+        return Err(JsValue::from_str(
+            "threshold must be between 1 and totalShares",
+        ));
+    }
+
+    let mut sk = match (request.secretKey, request.seed) {
+        (Some(sk), _) => Fr::from(sk),
+        (None, Some(seed)) => gen_sk(seed.as_slice()),
+        (None, None) => {
+            return Err(JsValue::from_str(
+                "Either secretKey or seed must be specified",
+            ))
+        }
+    };
+
+    // f(x) = sk + a_1*x + ... + a_{t-1}*x^{t-1}, so f(0) == sk
+    let mut rng = thread_rng();
+    let mut coefficients = Vec::with_capacity(request.threshold);
+    coefficients.push(sk);
+    for _ in 1..request.threshold {
+        coefficients.push(random_fr(&mut rng));
+    }
+
+    let commitments: Vec<G2> = coefficients
+        .iter()
+        .map(|a| {
+            let mut c = G2::one();
+            c.mul_assign(*a);
+            c
+        })
+        .collect();
+    let mut commitment_bytes = Vec::with_capacity(commitments.len());
+    for c in &commitments {
+        let mut bytes = Vec::new();
+        c.serialize(&mut bytes, true).unwrap();
+        commitment_bytes.push(bytes);
+    }
+
+    let mut shares = Vec::with_capacity(request.totalShares);
+    for i in 1..=request.totalShares {
+        let mut value = eval_polynomial(&coefficients, fr_from_index(i));
+        shares.push(BlsKeyShare {
+            index: i,
+            share: SecretKey::from(value),
+        });
+        value.zeroize();
+    }
+
+    // The dealer's polynomial is never needed again once every share has
+    // been derived from it, so wipe it (and the original secret it was
+    // seeded with) from memory rather than leaving copies on the stack.
+    coefficients.zeroize();
+    sk.zeroize();
+
+    Ok(serde_wasm_bindgen::to_value(&BlsSplitKeyResponse {
+        shares,
+        commitments: commitment_bytes,
+    })
+    .unwrap())
+}
+
+/// Reconstructs a BBS issuer secret key from at least `threshold` verified
+/// Shamir shares via Lagrange interpolation at `x = 0`. Every share is
+/// checked against the Feldman commitments before it is used, so a
+/// malicious or corrupted share fails closed rather than silently
+/// producing the wrong key.
+#[wasm_bindgen(js_name = blsReconstructKey)]
+pub async fn bls_reconstruct_key(request: JsValue) -> Result<JsValue, JsValue> {
+    set_panic_hook();
+    let request: BlsReconstructKeyRequest = request.try_into()?;
+    if request.shares.is_empty() {
+        return Err(JsValue::from_str("At least one share is required"));
+    }
+
+    let mut commitments = Vec::with_capacity(request.commitments.len());
+    for bytes in &request.commitments {
+        match G2::deserialize(&mut bytes.as_slice(), true) {
+            Ok(c) => commitments.push(c),
+            Err(e) => return Err(JsValue::from(&format!("{:?}", e))),
+        }
+    }
+    if request.shares.len() < commitments.len() {
+        return Err(JsValue::from_str(
+            "fewer shares than the threshold were provided",
+        ));
+    }
+
+    let mut seen_indices = BTreeSet::new();
+    for share in &request.shares {
+        if share.index == 0 {
+            return Err(JsValue::from_str("share index must be >= 1"));
+        }
+        if !seen_indices.insert(share.index) {
+            return Err(JsValue::from_str("duplicate share index"));
+        }
+        if !verify_share(share.index, &Fr::from(share.share.clone()), &commitments) {
+            return Err(JsValue::from_str("share failed Feldman verification"));
+        }
+    }
+
+    let indices: Vec<Fr> = request.shares.iter().map(|s| fr_from_index(s.index)).collect();
+    let mut secret = Fr::zero();
+    for (i, share) in request.shares.iter().enumerate() {
+        // Lagrange coefficient for point i evaluated at x = 0:
+        // L_i(0) = prod_{j != i} (-x_j) / (x_i - x_j)
+        let mut lagrange = Fr::one();
+        for (j, _) in request.shares.iter().enumerate() {
+            if i == j {
+                continue;
+            }
+            let mut numerator = indices[j];
+            numerator.negate();
+            let mut denominator = indices[i];
+            denominator.sub_assign(&indices[j]);
+            let mut denominator_inv = match denominator.inverse() {
+                Some(inv) => inv,
+                None => return Err(JsValue::from_str("duplicate share index")),
+            };
+            numerator.mul_assign(&denominator_inv);
+            lagrange.mul_assign(&numerator);
+            numerator.zeroize();
+            denominator.zeroize();
+            denominator_inv.zeroize();
+        }
+        let mut share_value = Fr::from(share.share.clone());
+        let mut term = share_value;
+        term.mul_assign(&lagrange);
+        secret.add_assign(&term);
+        lagrange.zeroize();
+        term.zeroize();
+        share_value.zeroize();
+    }
+
+    let reconstructed = SecretKey::from(secret);
+    secret.zeroize();
+    Ok(serde_wasm_bindgen::to_value(&reconstructed).unwrap())
+}
+
 /// Creates a BBS+ PoK from termwise-encoded multiple credentials
 #[wasm_bindgen(js_name = blsCreateProofMulti)]
 pub async fn bls_create_proof_multi(request: JsValue) -> Result<JsValue, JsValue> {
@@ -399,6 +986,7 @@ pub async fn bls_create_proof_multi(request: JsValue) -> Result<JsValue, JsValue
     let mut poks: Vec<PoKOfSignature> = Vec::with_capacity(num_of_inputs);
     let mut message_counts: Vec<usize> = Vec::with_capacity(num_of_inputs);
     let mut revealeds: Vec<Vec<usize>> = Vec::with_capacity(num_of_inputs);
+    let mut pk_bytes_list: Vec<Vec<u8>> = Vec::with_capacity(num_of_inputs);
 
     // generate blindings and hashmaps based on request.equivs
     let equiv_blindings: Vec<ProofNonce> = request
@@ -450,6 +1038,7 @@ pub async fn bls_create_proof_multi(request: JsValue) -> Result<JsValue, JsValue
         match PoKOfSignature::init(&r_signature, &pk, messages.as_slice()) {
             Err(e) => return Err(JsValue::from(&format!("{:?}", e))),
             Ok(pok) => {
+                pk_bytes_list.push(pk.to_bytes_compressed_form());
                 poks.push(pok);
                 message_counts.push(r_messages.len());
                 revealeds.push(r_revealed);
@@ -458,17 +1047,21 @@ pub async fn bls_create_proof_multi(request: JsValue) -> Result<JsValue, JsValue
     }
 
     // (2) challenge
-    let mut challenge_bytes = Vec::new();
-    for pok in &poks {
-        challenge_bytes.extend_from_slice(pok.to_bytes().as_slice());
-    }
-    if request.nonce.is_empty() {
-        challenge_bytes.extend_from_slice(&[0u8; FR_COMPRESSED_SIZE]);
-    } else {
-        let nonce = ProofNonce::hash(&request.nonce);
-        challenge_bytes.extend_from_slice(nonce.to_bytes_uncompressed_form().as_ref());
+    let mut transcript = Transcript::new(PROOF_TRANSCRIPT_LABEL_MULTI);
+    for (((pok, pk_bytes), message_count), revealed) in poks
+        .iter()
+        .zip(pk_bytes_list.iter())
+        .zip(message_counts.iter())
+        .zip(revealeds.iter())
+    {
+        transcript.append_message(pk_bytes);
+        transcript.append_u64(*message_count as u64);
+        transcript.append_indices(&BTreeSet::from_iter(revealed.iter().cloned()));
+        transcript.append_message(&pok.to_bytes());
     }
-    let challenge_hash = ProofChallenge::hash(&challenge_bytes);
+    transcript.append_message(&nonce_transcript_bytes(&request.nonce));
+    transcript.append_message(&request.presentationHeader);
+    let challenge_hash = transcript.challenge();
 
     // (3) response
     let mut proofs: Vec<PoKOfSignatureProofMultiWrapper> = Vec::with_capacity(num_of_inputs);
@@ -512,54 +1105,322 @@ pub async fn bls_verify_proof_multi(request: JsValue) -> Result<JsValue, JsValue
         ));
     }
 
-    let nonce = if request.nonce.is_empty() {
-        ProofNonce::default()
-    } else {
-        ProofNonce::hash(&request.nonce)
-    };
-
-    // (1) generate challenge hash
+    // (1) recompute the shared challenge by absorbing every proof's
+    // challenge contribution in the same order the prover used
+    let mut pks = Vec::with_capacity(num_of_inputs);
+    let mut revealeds: Vec<BTreeSet<usize>> = Vec::with_capacity(num_of_inputs);
+    let mut revealed_messages_list: Vec<BTreeMap<usize, SignatureMessage>> =
+        Vec::with_capacity(num_of_inputs);
+    let mut proofs = Vec::with_capacity(num_of_inputs);
 
-    // (2) verify
-    for (i, (r_messages, r_proof, r_pk)) in
-        multizip((request.messages, request.proof, request.publicKey)).enumerate()
+    let mut transcript = Transcript::new(PROOF_TRANSCRIPT_LABEL_MULTI);
+    for (r_messages, r_proof, r_pk) in
+        multizip((request.messages, request.proof, request.publicKey))
     {
+        let message_count = u16::from_be_bytes(*array_ref![r_proof.bit_vector, 0, 2]) as usize;
         let pk = r_pk.to_public_key(message_count)?;
-        let messages = request.messages.clone();
-        let (revealed, proof) = request.proof.unwrap();
-        let proof_request = ProofRequest {
-            revealed_messages: revealed,
-            verification_key: pk,
-        };
+        let (revealed, proof) = r_proof.unwrap();
 
-        let revealed_vec = proof_request
-            .revealed_messages
-            .iter()
-            .collect::<Vec<&usize>>();
+        let revealed_vec = revealed.iter().collect::<Vec<&usize>>();
         let mut revealed_messages = BTreeMap::new();
         for i in 0..revealed_vec.len() {
             revealed_messages.insert(
                 *revealed_vec[i],
-                SignatureMessage::hash(messages[i].clone()),
+                SignatureMessage::hash(r_messages[i].clone()),
             );
         }
 
-        let signature_proof = SignatureProof {
-            revealed_messages,
-            proof,
-        };
+        transcript.append_message(&pk.to_bytes_compressed_form());
+        transcript.append_u64(message_count as u64);
+        transcript.append_indices(&revealed);
+        transcript.append_message(&proof.get_bytes_for_challenge(revealed.clone(), &pk));
 
-        Ok(serde_wasm_bindgen::to_value(&BbsVerifyResponse {
-            verified: Verifier::verify_signature_pok(&proof_request, &signature_proof, &nonce)
-                .is_ok(),
-            error: None,
-        })
-        .unwrap());
+        pks.push(pk);
+        revealeds.push(revealed);
+        revealed_messages_list.push(revealed_messages);
+        proofs.push(proof);
+    }
+    transcript.append_message(&nonce_transcript_bytes(&request.nonce));
+    transcript.append_message(&request.presentationHeader);
+    let challenge_hash = transcript.challenge();
+
+    // (2) verify each proof against the shared challenge
+    for i in 0..num_of_inputs {
+        let verified = proofs[i]
+            .verify(&pks[i], &revealed_messages_list[i], &challenge_hash)
+            .unwrap_or(false);
+        if !verified {
+            return Ok(serde_wasm_bindgen::to_value(&BbsVerifyResponse {
+                verified: false,
+                error: None,
+            })
+            .unwrap());
+        }
+    }
+
+    // (3) verify cross-credential equivalence: linked hidden attributes
+    // share the same external blinding factor, so their Schnorr responses
+    // must be equal without either attribute being revealed.
+    for group in &request.equivs {
+        let mut expected: Option<SignatureMessage> = None;
+        for &(credential_index, message_index) in group {
+            if credential_index >= num_of_inputs {
+                return Ok(serde_wasm_bindgen::to_value(&BbsVerifyResponse {
+                    verified: false,
+                    error: Some("equivs references an unknown credential".to_string()),
+                })
+                .unwrap());
+            }
+            let response = match proofs[credential_index]
+                .get_resp_for_message(message_index, &revealeds[credential_index])
+            {
+                Ok(r) => r,
+                Err(e) => {
+                    return Ok(serde_wasm_bindgen::to_value(&BbsVerifyResponse {
+                        verified: false,
+                        error: Some(format!("{:?}", e)),
+                    })
+                    .unwrap())
+                }
+            };
+            match expected {
+                None => expected = Some(response),
+                Some(e) if e != response => {
+                    return Ok(serde_wasm_bindgen::to_value(&BbsVerifyResponse {
+                        verified: false,
+                        error: None,
+                    })
+                    .unwrap())
+                }
+                _ => {}
+            }
+        }
     }
 
     Ok(serde_wasm_bindgen::to_value(&BbsVerifyResponse {
-        verified: false,
+        verified: true,
         error: None,
     })
     .unwrap())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    wasm_bindgen_test_configure!(run_in_browser);
+
+    /// Builds a signed BBS+ credential over `messages` for a freshly
+    /// generated issuer key, keyed off `seed` so tests get independent
+    /// issuers without going through the async JS-facing key generation
+    /// entry points.
+    fn bbs_credential(seed: &[u8], messages: &[Vec<u8>]) -> (Signature, DeterministicPublicKey) {
+        let sk = gen_sk(seed);
+        let (dpk, sk) = DeterministicPublicKey::new(Some(KeyGenOption::FromSecretKey(sk)));
+        let pk = dpk.to_public_key(messages.len()).unwrap();
+        let hashed: Vec<SignatureMessage> = messages.iter().map(SignatureMessage::hash).collect();
+        let signature = Signature::new(hashed.as_slice(), &sk, &pk).unwrap();
+        (signature, dpk)
+    }
+
+    /// `blsVerifyProofMulti` expects the non-multi `PoKOfSignatureProofWrapper`
+    /// encoding, while `blsCreateProofMulti` emits the multi-specific
+    /// wrapper; bridge the two the same way a JS caller would when wiring
+    /// the two endpoints together.
+    fn to_verify_wrapper(
+        message_count: usize,
+        multi: PoKOfSignatureProofMultiWrapper,
+    ) -> PoKOfSignatureProofWrapper {
+        let revealed: BTreeSet<usize> = multi.revealed.into_iter().collect();
+        PoKOfSignatureProofWrapper::new(message_count, &revealed, multi.proof)
+    }
+
+    async fn create_and_verify(
+        messages_a: Vec<Vec<u8>>,
+        messages_b: Vec<Vec<u8>>,
+        equivs: Vec<Vec<(usize, usize)>>,
+    ) -> BbsVerifyResponse {
+        let (sig_a, dpk_a) = bbs_credential(b"test-credential-a-seed", &messages_a);
+        let (sig_b, dpk_b) = bbs_credential(b"test-credential-b-seed", &messages_b);
+
+        let create_request = BlsCreateProofMultiRequest {
+            signature: vec![sig_a, sig_b],
+            publicKey: vec![dpk_a.clone(), dpk_b.clone()],
+            messages: vec![messages_a.clone(), messages_b.clone()],
+            revealed: vec![vec![0], vec![0]],
+            nonce: b"test-nonce".to_vec(),
+            equivs,
+            presentationHeader: Vec::new(),
+        };
+        let created = bls_create_proof_multi(serde_wasm_bindgen::to_value(&create_request).unwrap())
+            .await
+            .unwrap();
+        let multi_proofs: Vec<PoKOfSignatureProofMultiWrapper> =
+            serde_wasm_bindgen::from_value(created).unwrap();
+
+        let proofs: Vec<PoKOfSignatureProofWrapper> = multi_proofs
+            .into_iter()
+            .zip([messages_a.len(), messages_b.len()])
+            .map(|(proof, message_count)| to_verify_wrapper(message_count, proof))
+            .collect();
+
+        let verify_request = BlsVerifyProofMultiContext {
+            proof: proofs,
+            publicKey: vec![dpk_a, dpk_b],
+            messages: vec![vec![messages_a[0].clone()], vec![messages_b[0].clone()]],
+            nonce: b"test-nonce".to_vec(),
+            equivs: create_request.equivs.clone(),
+            presentationHeader: Vec::new(),
+        };
+        let verified = bls_verify_proof_multi(serde_wasm_bindgen::to_value(&verify_request).unwrap())
+            .await
+            .unwrap();
+        serde_wasm_bindgen::from_value(verified).unwrap()
+    }
+
+    #[wasm_bindgen_test]
+    async fn verify_proof_multi_accepts_matching_equivalence() {
+        let shared = b"shared-attribute".to_vec();
+        let messages_a = vec![b"revealed-a".to_vec(), shared.clone()];
+        let messages_b = vec![b"revealed-b".to_vec(), shared];
+        let equivs = vec![vec![(0, 1), (1, 1)]];
+
+        let response = create_and_verify(messages_a, messages_b, equivs).await;
+        assert!(response.verified);
+    }
+
+    #[wasm_bindgen_test]
+    async fn verify_proof_multi_rejects_tampered_equivalence() {
+        let messages_a = vec![b"revealed-a".to_vec(), b"attribute-one".to_vec()];
+        let messages_b = vec![b"revealed-b".to_vec(), b"attribute-two".to_vec()];
+        let equivs = vec![vec![(0, 1), (1, 1)]];
+
+        let response = create_and_verify(messages_a, messages_b, equivs).await;
+        assert!(!response.verified);
+    }
+
+    #[wasm_bindgen_test]
+    async fn split_and_reconstruct_key_round_trips() {
+        let secret_key = SecretKey::from(gen_sk(b"split-reconstruct-seed"));
+
+        let split_request = BlsSplitKeyRequest {
+            secretKey: Some(secret_key.clone()),
+            seed: None,
+            threshold: 3,
+            totalShares: 5,
+        };
+        let split_response = bls_split_key(serde_wasm_bindgen::to_value(&split_request).unwrap())
+            .await
+            .unwrap();
+        let split: BlsSplitKeyResponse = serde_wasm_bindgen::from_value(split_response).unwrap();
+
+        let reconstruct_request = BlsReconstructKeyRequest {
+            shares: split.shares[0..3].to_vec(),
+            commitments: split.commitments,
+        };
+        let reconstructed = bls_reconstruct_key(
+            serde_wasm_bindgen::to_value(&reconstruct_request).unwrap(),
+        )
+        .await
+        .unwrap();
+        let reconstructed_key: SecretKey = serde_wasm_bindgen::from_value(reconstructed).unwrap();
+
+        assert_eq!(
+            reconstructed_key.to_bytes_compressed_form(),
+            secret_key.to_bytes_compressed_form()
+        );
+    }
+
+    #[wasm_bindgen_test]
+    async fn reconstruct_key_rejects_share_with_wrong_index() {
+        let split_request = BlsSplitKeyRequest {
+            secretKey: None,
+            seed: Some(b"tampered-share-seed".to_vec()),
+            threshold: 2,
+            totalShares: 3,
+        };
+        let split_response = bls_split_key(serde_wasm_bindgen::to_value(&split_request).unwrap())
+            .await
+            .unwrap();
+        let mut split: BlsSplitKeyResponse = serde_wasm_bindgen::from_value(split_response).unwrap();
+
+        // Relabel a share under another share's index without updating its
+        // value: the Feldman check must catch the mismatch rather than
+        // silently reconstructing the wrong key.
+        split.shares[0].index = split.shares[1].index;
+
+        let reconstruct_request = BlsReconstructKeyRequest {
+            shares: split.shares[0..2].to_vec(),
+            commitments: split.commitments,
+        };
+        let result =
+            bls_reconstruct_key(serde_wasm_bindgen::to_value(&reconstruct_request).unwrap())
+                .await;
+        assert!(result.is_err());
+    }
+
+    #[wasm_bindgen_test]
+    async fn blind_issuance_round_trips() {
+        let sk = gen_sk(b"blind-issuance-seed");
+        let (dpk, sk) = DeterministicPublicKey::new(Some(KeyGenOption::FromSecretKey(sk)));
+        let message_count = 2;
+        let hidden_message = b"holder-secret-attribute".to_vec();
+        let known_message = b"issuer-known-attribute".to_vec();
+
+        let commitment_request = BlsBlindCommitmentRequest {
+            publicKey: dpk.clone(),
+            messageCount: message_count,
+            hidden: vec![1],
+            messages: vec![hidden_message.clone()],
+            nonce: b"blind-issuance-nonce".to_vec(),
+        };
+        let commitment_response = bls_blind_commitment(
+            serde_wasm_bindgen::to_value(&commitment_request).unwrap(),
+        )
+        .await
+        .unwrap();
+        let commitment_context: BlsBlindCommitmentContext =
+            serde_wasm_bindgen::from_value(commitment_response).unwrap();
+
+        let sign_request = BlsBlindSignRequest {
+            commitment: commitment_context.commitment,
+            keyPair: BlsKeyPair {
+                publicKey: Some(dpk.to_bytes_compressed_form().to_vec()),
+                secretKey: Some(sk),
+            },
+            messageCount: message_count,
+            known: vec![0],
+            hidden: vec![1],
+            messages: vec![known_message.clone()],
+            nonce: b"blind-issuance-nonce".to_vec(),
+        };
+        let blind_signature = bls_blind_sign(serde_wasm_bindgen::to_value(&sign_request).unwrap())
+            .await
+            .unwrap();
+        let blind_signature: BlindSignature =
+            serde_wasm_bindgen::from_value(blind_signature).unwrap();
+
+        let unblind_request = BlsUnblindSignatureRequest {
+            signature: blind_signature,
+            blindingFactor: commitment_context.blindingFactor,
+        };
+        let signature = bls_unblind_signature(
+            serde_wasm_bindgen::to_value(&unblind_request).unwrap(),
+        )
+        .await
+        .unwrap();
+        let signature: Signature = serde_wasm_bindgen::from_value(signature).unwrap();
+
+        let verify_request = BlsBbsVerifyRequest {
+            publicKey: dpk,
+            signature,
+            messages: vec![known_message, hidden_message],
+        };
+        let verified = bls_verify(serde_wasm_bindgen::to_value(&verify_request).unwrap())
+            .await
+            .unwrap();
+        let verified: BbsVerifyResponse = serde_wasm_bindgen::from_value(verified).unwrap();
+        assert!(verified.verified);
+    }
+}